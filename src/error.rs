@@ -0,0 +1,89 @@
+//! Structured error type returned by the LST-file parsers.
+
+use std::fmt;
+
+/// Errors that can occur while parsing an LST file.
+///
+/// Marked `#[non_exhaustive]` so new failure modes can be added without breaking matches in
+/// downstream crates. The attribute has no effect inside this crate: the Python bindings map
+/// each variant to a specific Python exception with no wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LstError {
+    /// The TDC type in the header was not recognized.
+    UnknownTdc(String),
+    /// No calibration factor (`calfact=`) was found for the given channel.
+    MissingCalFactor { channel: u8 },
+    /// The `mpafmt=` entry in the header is missing or not recognized.
+    UnknownDataFormat,
+    /// The `[DATA]` marker separating header from data was not found.
+    MissingDataMarker,
+    /// No `time_patch=` entry was found in the header.
+    MissingTimePatch,
+    /// The `time_patch=` value found in the header is not implemented.
+    UnimplementedTimePatch(String),
+    /// No shot range (`range=`) was found for the given channel.
+    MissingShotRange { channel: u8 },
+    /// No acquisition timestamp (`cmline0=`) was found for the given channel.
+    MissingTimestamp { channel: u8 },
+    /// A numeric header value could not be parsed.
+    NumberParse(String),
+    /// The acquisition timestamp could not be parsed.
+    TimestampParse(String),
+    /// An I/O error occurred while reading the LST file.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for LstError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownTdc(tdc) => write!(f, "Unknown TDC type: {tdc}"),
+            Self::MissingCalFactor { channel } => {
+                write!(f, "Could not find calibration factor for channel {channel}")
+            }
+            Self::UnknownDataFormat => write!(f, "Could not find a known data format."),
+            Self::MissingDataMarker => write!(f, "Could not find [DATA] marker."),
+            Self::MissingTimePatch => write!(f, "Could not find time patch information."),
+            Self::UnimplementedTimePatch(time_patch) => write!(
+                f,
+                "The found time patch information {time_patch} is not implemented."
+            ),
+            Self::MissingShotRange { channel } => {
+                write!(f, "Could not find shot range for channel {channel}")
+            }
+            Self::MissingTimestamp { channel } => {
+                write!(f, "Could not find timestamp for channel {channel}")
+            }
+            Self::NumberParse(msg) => write!(f, "Could not parse number: {msg}"),
+            Self::TimestampParse(msg) => write!(f, "Could not parse timestamp: {msg}"),
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LstError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LstError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<std::num::ParseFloatError> for LstError {
+    fn from(err: std::num::ParseFloatError) -> Self {
+        Self::NumberParse(err.to_string())
+    }
+}
+
+impl From<std::num::ParseIntError> for LstError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        Self::NumberParse(err.to_string())
+    }
+}
@@ -6,11 +6,15 @@
 //! The `lib.rs` only holds re-exports and interaction functions for python specific
 //! things. All other functionality is implemented in submodules.
 
+mod error;
 mod lst;
 
 // Re-export
+pub use error::LstError;
 pub use lst::LSTFile;
 
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::{PyLookupError, PyOSError, PyValueError};
 use pyo3::prelude::*;
 
 /// Prints a message.
@@ -19,8 +23,70 @@ fn hello() -> PyResult<String> {
     Ok("Hello from rimseval!".into())
 }
 
+/// Convert an [`LstError`] into the Python exception that best matches its failure mode.
+///
+/// I/O failures become an `OSError`, missing header entries become a `LookupError`, and
+/// malformed header values (an unrecognized enum-ish value or a value that failed to parse)
+/// become a `ValueError`.
+fn lst_error_to_py(err: LstError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        LstError::Io(_) => PyOSError::new_err(message),
+        LstError::MissingCalFactor { .. }
+        | LstError::MissingDataMarker
+        | LstError::MissingTimePatch
+        | LstError::MissingShotRange { .. }
+        | LstError::MissingTimestamp { .. } => PyLookupError::new_err(message),
+        LstError::UnknownTdc(_)
+        | LstError::UnknownDataFormat
+        | LstError::UnimplementedTimePatch(_)
+        | LstError::NumberParse(_)
+        | LstError::TimestampParse(_) => PyValueError::new_err(message),
+    }
+}
+
+/// Python-facing wrapper around [`LSTFile`], exposing the hot binning loop to Python.
+#[pyclass(name = "LSTFile")]
+struct PyLSTFile(LSTFile);
+
+#[pymethods]
+impl PyLSTFile {
+    #[new]
+    #[pyo3(signature = (file_path, channel, tag_channel=None))]
+    fn new(file_path: &str, channel: u8, tag_channel: Option<u8>) -> PyResult<Self> {
+        LSTFile::open(file_path, channel, tag_channel)
+            .map(Self)
+            .map_err(lst_error_to_py)
+    }
+
+    /// Bin the decoded time-of-flight values into a time-of-flight histogram.
+    ///
+    /// # Returns
+    /// A tuple of numpy arrays `(tof_ns, counts)`.
+    fn time_histogram<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> (Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<u64>>) {
+        let (tof_ns, counts) = self.0.time_histogram();
+        (tof_ns.into_pyarray_bound(py), counts.into_pyarray_bound(py))
+    }
+
+    /// Convert time-of-flight values, in nanoseconds, to mass using the calibration factor.
+    fn to_mass<'py>(
+        &self,
+        py: Python<'py>,
+        tof_ns: PyReadonlyArray1<f64>,
+    ) -> PyResult<Bound<'py, PyArray1<f64>>> {
+        let tof_ns = tof_ns
+            .as_slice()
+            .map_err(|_| PyValueError::new_err("tof_ns must be a contiguous array"))?;
+        Ok(self.0.to_mass(tof_ns).into_pyarray_bound(py))
+    }
+}
+
 #[pymodule]
 fn _lowlevel(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(hello, m)?)?;
+    m.add_class::<PyLSTFile>()?;
     Ok(())
 }
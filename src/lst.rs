@@ -1,11 +1,20 @@
 //! This module reads the LST files and serves data to later write.
 
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 
-use anyhow::{anyhow, Result};
+use rayon::prelude::*;
 use time::PrimitiveDateTime;
 
+use crate::error::LstError;
+
+/// Convenience alias for results returned by the LST parsers.
+type Result<T> = std::result::Result<T, LstError>;
+
+/// Marker that separates the header section of an LST file from the data section.
+const DATA_MARKER: &str = "[DATA]";
+
 /// Represents the bin-width of one channel of the TDC in ps.
 struct BinWidth {
     value_ps: u32,
@@ -29,7 +38,7 @@ impl BinWidth {
         match header[0].to_lowercase() {
             x if x.contains("mpa4a") => value_ps = 100,
             x if x.contains("mcs8a") => value_ps = 80,
-            _ => return Err(anyhow!("Unknown TDC type: {}", header[0])),
+            _ => return Err(LstError::UnknownTdc(header[0].clone())),
         };
 
         Ok(Self { value_ps })
@@ -67,10 +76,7 @@ impl CalFactor {
                 return Ok(Self { value });
             };
         }
-        Err(anyhow!(
-            "Could not find calibration factor for channel {}",
-            channel
-        ))
+        Err(LstError::MissingCalFactor { channel })
     }
 }
 
@@ -104,11 +110,11 @@ impl DataFormat {
                 match line.split("=").last().unwrap() {
                     "dat" => return Ok(Self::BINARY),
                     "asc" => return Ok(Self::ASCII),
-                    _ => return Err(anyhow!("Unknown data format.")),
+                    _ => return Err(LstError::UnknownDataFormat),
                 };
             };
         }
-        Err(anyhow!("Could not find data format."))
+        Err(LstError::UnknownDataFormat)
     }
 }
 
@@ -120,6 +126,24 @@ struct BinaryRange {
     stop: u32,
 }
 
+/// Implementation of the BinaryRange structure.
+impl BinaryRange {
+    /// Extract the field described by this range from a binary data word.
+    ///
+    /// The field is extracted by shifting the word so that `start` lines up with bit zero
+    /// and then masking off everything above `stop - start` bits.
+    ///
+    /// # Arguments
+    /// * `word` - The binary data word the field is encoded in.
+    ///
+    /// # Returns
+    /// The extracted field, right-aligned in the returned `u64`.
+    fn extract(&self, word: u64) -> u64 {
+        let mask = (1u64 << (self.stop - self.start)) - 1;
+        (word >> self.start) & mask
+    }
+}
+
 /// Structure to hold the time patch information.
 ///
 /// The time patch is specific for the list file and states how the data is stored.
@@ -184,13 +208,8 @@ impl TimePatch {
                     stop: 48,
                 };
             }
-            "" => return Err(anyhow!("Could not find time patch information.")),
-            _ => {
-                return Err(anyhow!(
-                    "The found time patch information {} is not implemented.",
-                    time_patch
-                ))
-            }
+            "" => return Err(LstError::MissingTimePatch),
+            _ => return Err(LstError::UnimplementedTimePatch(time_patch.to_owned())),
         };
 
         Ok(Self {
@@ -240,21 +259,196 @@ impl ShotRange {
                 return Ok(Self { value });
             };
         }
-        Err(anyhow!("Could not find shot range for channel {}", channel))
+        Err(LstError::MissingShotRange { channel })
     }
 }
 
-/// Enum to hold the read state of the LST file.
-enum ReadState {
-    Header,
-    Data,
-}
-
 /// Enum to hold the data of the LST file.
+///
+/// The binary variant holds the decoded events as three parallel vectors, one entry per event:
+/// the sweep number, the time-of-flight channel, and the detector/TDC channel.
 #[derive(Debug)]
 enum LSTData {
     ASCII(Vec<String>),
-    Binary(Vec<u32>),
+    Binary {
+        sweep: Vec<u32>,
+        time: Vec<u32>,
+        channel: Vec<u32>,
+    },
+}
+
+/// Data sections at or above this size are decoded with [`decode_binary_parallel`] instead of
+/// [`decode_binary_sequential`]; below it, the overhead of splitting into chunks and spawning
+/// `rayon` tasks outweighs the benefit.
+const PARALLEL_DECODE_THRESHOLD_BYTES: usize = 64 * 1024 * 1024;
+
+/// Number of words decoded per `rayon` chunk by [`decode_binary_parallel`].
+const PARALLEL_DECODE_CHUNK_WORDS: usize = 1 << 16;
+
+/// Decode a raw binary data section into sweep, time, and channel vectors.
+///
+/// Dispatches to the sequential or parallel decoder depending on the size of `data`, see
+/// [`PARALLEL_DECODE_THRESHOLD_BYTES`].
+///
+/// # Arguments
+/// * `data` - Raw bytes of the `[DATA]` section.
+/// * `time_patch` - Time patch information describing the word layout.
+///
+/// # Returns
+/// The decoded `LSTData::Binary` variant.
+fn decode_binary(data: &[u8], time_patch: &TimePatch) -> LSTData {
+    if data.len() >= PARALLEL_DECODE_THRESHOLD_BYTES {
+        decode_binary_parallel(data, time_patch)
+    } else {
+        decode_binary_sequential(data, time_patch)
+    }
+}
+
+/// Decode a raw binary data section into sweep, time, and channel vectors, single-threaded.
+///
+/// The data section is walked in fixed-size words of `time_patch.binary_width / 8` bytes.
+/// Each word is interpreted little-endian and the three fields are extracted via the
+/// `time_patch`'s binary ranges. Words whose channel field is zero are padding/sync words
+/// and are skipped, as are any trailing bytes that don't fill a full word.
+///
+/// # Arguments
+/// * `data` - Raw bytes of the `[DATA]` section.
+/// * `time_patch` - Time patch information describing the word layout.
+///
+/// # Returns
+/// The decoded `LSTData::Binary` variant.
+fn decode_binary_sequential(data: &[u8], time_patch: &TimePatch) -> LSTData {
+    let word_width = (time_patch.binary_width / 8) as usize;
+
+    let mut sweep = Vec::new();
+    let mut time = Vec::new();
+    let mut channel = Vec::new();
+
+    for word_bytes in data.chunks(word_width) {
+        if word_bytes.len() < word_width {
+            break;
+        }
+
+        let mut buf = [0u8; 8];
+        buf[..word_bytes.len()].copy_from_slice(word_bytes);
+        let word = u64::from_le_bytes(buf);
+
+        let ch = time_patch.channel_range.extract(word);
+        if ch == 0 {
+            continue;
+        }
+
+        sweep.push(time_patch.sweep_range.extract(word) as u32);
+        time.push(time_patch.time_range.extract(word) as u32);
+        channel.push(ch as u32);
+    }
+
+    LSTData::Binary {
+        sweep,
+        time,
+        channel,
+    }
+}
+
+/// Decode a raw binary data section into sweep, time, and channel vectors, using `rayon` to
+/// decode word-aligned chunks in parallel.
+///
+/// Sweep counters are monotonic within the file, so chunks can be decoded independently with
+/// [`decode_binary_sequential`] and concatenated in order without any cross-chunk renumbering,
+/// as long as every chunk boundary falls on a word boundary.
+///
+/// # Arguments
+/// * `data` - Raw bytes of the `[DATA]` section.
+/// * `time_patch` - Time patch information describing the word layout.
+///
+/// # Returns
+/// The decoded `LSTData::Binary` variant.
+fn decode_binary_parallel(data: &[u8], time_patch: &TimePatch) -> LSTData {
+    let word_width = (time_patch.binary_width / 8) as usize;
+    let chunk_bytes = PARALLEL_DECODE_CHUNK_WORDS * word_width;
+
+    let decoded: Vec<(Vec<u32>, Vec<u32>, Vec<u32>)> = data
+        .par_chunks(chunk_bytes)
+        .map(|chunk| match decode_binary_sequential(chunk, time_patch) {
+            LSTData::Binary {
+                sweep,
+                time,
+                channel,
+            } => (sweep, time, channel),
+            LSTData::ASCII(_) => unreachable!("decode_binary_sequential always returns Binary"),
+        })
+        .collect();
+
+    let total_events = decoded.iter().map(|(sweep, _, _)| sweep.len()).sum();
+    let mut sweep = Vec::with_capacity(total_events);
+    let mut time = Vec::with_capacity(total_events);
+    let mut channel = Vec::with_capacity(total_events);
+    for (chunk_sweep, chunk_time, chunk_channel) in decoded {
+        sweep.extend(chunk_sweep);
+        time.extend(chunk_time);
+        channel.extend(chunk_channel);
+    }
+
+    LSTData::Binary {
+        sweep,
+        time,
+        channel,
+    }
+}
+
+/// Signal events on the data channel, partitioned by whether their shot also carries a tag.
+///
+/// A shot is identified by its sweep number. A shot is "tagged" if at least one event on the
+/// tag channel occurred during that sweep; all signal events from that sweep are then grouped
+/// under `tagged`, keyed by sweep number, and otherwise under `untagged`.
+#[derive(Debug, Default, PartialEq)]
+pub struct TaggedEvents {
+    pub tagged: HashMap<u32, Vec<u32>>,
+    pub untagged: HashMap<u32, Vec<u32>>,
+}
+
+/// Partition the binary signal events on `channel` into tagged and untagged groups.
+///
+/// Returns `None` if `data` is not binary, since ASCII events aren't decoded per-channel.
+///
+/// # Arguments
+/// * `data` - Decoded LST data.
+/// * `channel` - Channel the signal/data is in.
+/// * `tag_channel` - Channel that contains the tag signal.
+///
+/// # Returns
+/// The signal events split into tagged and untagged groups, keyed by sweep number.
+fn partition_tagged_events(data: &LSTData, channel: u8, tag_channel: u8) -> Option<TaggedEvents> {
+    let LSTData::Binary {
+        sweep,
+        time,
+        channel: channels,
+    } = data
+    else {
+        return None;
+    };
+
+    let tagged_sweeps: HashSet<u32> = sweep
+        .iter()
+        .zip(channels)
+        .filter(|(_, &ch)| ch == tag_channel as u32)
+        .map(|(&sw, _)| sw)
+        .collect();
+
+    let mut events = TaggedEvents::default();
+    for ((&sw, &t), &ch) in sweep.iter().zip(time).zip(channels) {
+        if ch != channel as u32 {
+            continue;
+        }
+        let group = if tagged_sweeps.contains(&sw) {
+            &mut events.tagged
+        } else {
+            &mut events.untagged
+        };
+        group.entry(sw).or_default().push(t);
+    }
+
+    Some(events)
 }
 
 /// Structure to hold the LST file data.
@@ -271,14 +465,15 @@ pub struct LSTFile {
     shot_range: ShotRange,
     pub time_stamp: PrimitiveDateTime,
     data: LSTData,
+    pub tagged_events: Option<TaggedEvents>,
+    channel: u8,
 }
 
 /// Implementation of the LSTFile structure.
 impl LSTFile {
     /// Create a new LSTFile from a given file path.
     ///
-    /// The file path is the path to the LST file that should be read.
-    /// The file is then read and the data is stored in the LSTFile structure.
+    /// Thin wrapper around [`LSTFile::from_reader`] that opens `file_path` and buffers it.
     ///
     /// # Arguments
     /// * `file_path` - Path to the LST file.
@@ -289,26 +484,42 @@ impl LSTFile {
     /// Result of a new LSTFile structure or an error if the file could not be read.
     pub fn open(file_path: &str, channel: u8, tag_channel: Option<u8>) -> Result<Self> {
         let file = File::open(file_path)?;
-        let file_metadata  = file.metadata()?;
-        let line_iter = BufReader::new(file).lines();
-        let mut header = Vec::with_capacity(200);
-
-        let mut data_in = Vec::new();
+        Self::from_reader(BufReader::new(file), channel, tag_channel)
+    }
 
-        let mut read_state = ReadState::Header;
-        for line in line_iter {
-            let line = line?;
-            if line.contains("[DATA]") {
-                read_state = ReadState::Data;
-                continue
+    /// Create a new LSTFile from any buffered reader.
+    ///
+    /// The header is scanned line by line until the `[DATA]` marker is found, at which point
+    /// reading switches from line-based to byte-based so that binary data sections are read
+    /// verbatim. This lets callers feed a `Cursor<Vec<u8>>`, a decompression stream, or any
+    /// other `BufRead` source without going through the filesystem.
+    ///
+    /// # Arguments
+    /// * `reader` - Buffered reader positioned at the start of the LST data.
+    /// * `channel` - Channel where the signal/data is in.
+    /// * `tag_channel` - Channel that contains the tag signal. If None, the data is untagged.
+    ///
+    /// # Returns
+    /// Result of a new LSTFile structure or an error if the data could not be read.
+    pub fn from_reader<R: BufRead>(
+        mut reader: R,
+        channel: u8,
+        tag_channel: Option<u8>,
+    ) -> Result<Self> {
+        let mut header = Vec::with_capacity(200);
+        loop {
+            let mut line = String::new();
+            let bytes_read = reader.read_line(&mut line)?;
+            if bytes_read == 0 {
+                return Err(LstError::MissingDataMarker);
             }
-            match read_state {
-                ReadState::Header => header.push(line),
-                ReadState::Data => data_in.push(line),
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.contains(DATA_MARKER) {
+                break;
             }
+            header.push(line.to_owned());
         }
 
-
         let bin_width = BinWidth::parse_header(&header)?;
         let calibration_factor = CalFactor::parse_header(&header, channel)?;
         let data_type = DataType {
@@ -318,16 +529,22 @@ impl LSTFile {
         let shot_range = ShotRange::parse_header(&header, channel)?;
         let time_stamp = find_timestamp(&header, channel)?;
 
-        let data: LSTData;
-        if data_type.format != DataFormat::ASCII {
-            return Err(anyhow!("Binary data not implemented yet."));
-        } else {
-            data = LSTData::ASCII(data_in);
-        }
+        let mut raw = Vec::new();
+        reader.read_to_end(&mut raw)?;
 
-        // FIXME: remove the next two lines
-        let LSTData::ASCII(x) = &data else { todo!() };
-        println!("{:?}", x[0]);
+        let data = match data_type.format {
+            DataFormat::ASCII => {
+                let lines = String::from_utf8_lossy(&raw)
+                    .lines()
+                    .map(|line| line.to_owned())
+                    .collect();
+                LSTData::ASCII(lines)
+            }
+            DataFormat::BINARY => decode_binary(&raw, &data_type.time_patch),
+        };
+
+        let tagged_events =
+            tag_channel.and_then(|tag_channel| partition_tagged_events(&data, channel, tag_channel));
 
         Ok(Self {
             bin_width,
@@ -336,11 +553,58 @@ impl LSTFile {
             shot_range,
             time_stamp,
             data,
+            tagged_events,
+            channel,
         })
+    }
+
+    /// Time-of-flight values (in TDC channel units) for all signal events.
+    ///
+    /// Returns an empty vector for ASCII data, since events aren't decoded per-channel there.
+    fn signal_times(&self) -> Vec<u32> {
+        match &self.data {
+            LSTData::Binary { time, channel, .. } => time
+                .iter()
+                .zip(channel)
+                .filter(|(_, &ch)| ch == self.channel as u32)
+                .map(|(&t, _)| t)
+                .collect(),
+            LSTData::ASCII(_) => Vec::new(),
+        }
+    }
+
+    /// Bin the decoded time-of-flight values into a time-of-flight histogram.
+    ///
+    /// Each bin corresponds to one TDC channel, converted to nanoseconds via `bin_width`.
+    ///
+    /// # Returns
+    /// A tuple `(tof_ns, counts)` where `tof_ns[i]` is the time-of-flight of bin `i` in
+    /// nanoseconds and `counts[i]` is the number of signal events that fell into it.
+    pub fn time_histogram(&self) -> (Vec<f64>, Vec<u64>) {
+        let times = self.signal_times();
+        let max_channel = times.iter().copied().max().unwrap_or(0) as usize;
+
+        let mut counts = vec![0u64; max_channel + 1];
+        for t in times {
+            counts[t as usize] += 1;
+        }
+
+        let bin_width_ns = self.bin_width.value_ps as f64 / 1_000.0;
+        let tof_ns = (0..counts.len()).map(|i| i as f64 * bin_width_ns).collect();
 
-        // TODO:
-        // 5. Parse the data for the data, if tagged, also for the data tag and store them in their
-        //    Vectors(?) and store them in their Vectors(?). (flesh this part out).
+        (tof_ns, counts)
+    }
+
+    /// Convert time-of-flight values, in nanoseconds, to mass using the stored calibration
+    /// factor.
+    ///
+    /// RIMSEval uses a quadratic time-of-flight to mass calibration: `mass = calibration_factor
+    /// * tof_ns^2`.
+    pub fn to_mass(&self, tof_ns: &[f64]) -> Vec<f64> {
+        tof_ns
+            .iter()
+            .map(|&t| self.calibration_factor.value * t * t)
+            .collect()
     }
 }
 
@@ -353,7 +617,8 @@ fn check_for_channel(line: &str, channel: u8) -> bool {
 fn find_timestamp(header: &Vec<String>, channel: u8) -> Result<PrimitiveDateTime> {
     let format = time::format_description::parse(
         "[month]/[day]/[year] [hour]:[minute]:[second].[subsecond]",
-    )?;
+    )
+    .map_err(|err| LstError::TimestampParse(err.to_string()))?;
     let mut correct_channel = false;
     for line in header {
         if check_for_channel(line, channel) {
@@ -368,11 +633,11 @@ fn find_timestamp(header: &Vec<String>, channel: u8) -> Result<PrimitiveDateTime
                 .next()
                 .unwrap()
                 .trim();
-            println!("Timestamp: {}", timestamp_in);
-            return Ok(PrimitiveDateTime::parse(&timestamp_in, &format)?);
+            return Ok(PrimitiveDateTime::parse(timestamp_in, &format)
+                .map_err(|err| LstError::TimestampParse(err.to_string()))?);
         };
     }
-    Err(anyhow!("Could not find timestamp for channel {}", channel))
+    Err(LstError::MissingTimestamp { channel })
 }
 
 #[cfg(test)]
@@ -391,4 +656,220 @@ mod tests {
         let err_bin_width = BinWidth::parse_header(&header);
         assert!(err_bin_width.is_err());
     }
+
+    #[test]
+    fn test_from_reader_binary_end_to_end() {
+        let channel = 1u8;
+        let sweep: u64 = 7;
+        let time: u64 = 12345;
+        let word = (channel as u64) << 60 | (time << 21) | (sweep << 1);
+
+        let mut raw = Vec::new();
+        raw.extend_from_slice(b"[MCS8A A]\r\n");
+        raw.extend_from_slice(b"CHN1,calfact=1.5\r\n");
+        raw.extend_from_slice(b"CHN1,range=100\r\n");
+        raw.extend_from_slice(b"CHN1,cmline0=08/31/2020 14:08:00.917-\r\n");
+        raw.extend_from_slice(b"mpafmt=dat\r\n");
+        raw.extend_from_slice(b"time_patch=9\r\n");
+        raw.extend_from_slice(b"[DATA]\r\n");
+        raw.extend_from_slice(&word.to_le_bytes());
+
+        let file = LSTFile::from_reader(std::io::Cursor::new(raw), channel, None).unwrap();
+
+        assert_eq!(
+            file.time_stamp,
+            time::macros::datetime!(2020-08-31 14:08:00.917)
+        );
+        let LSTData::Binary {
+            sweep: sweeps,
+            time: times,
+            channel: channels,
+        } = &file.data
+        else {
+            panic!("expected LSTData::Binary");
+        };
+        assert_eq!(sweeps, &vec![sweep as u32]);
+        assert_eq!(times, &vec![time as u32]);
+        assert_eq!(channels, &vec![channel as u32]);
+    }
+
+    #[test]
+    fn test_decode_binary_time_patch_9() {
+        let header = vec!["time_patch=9".to_owned()];
+        let time_patch = TimePatch::parse_header(&header).unwrap();
+
+        let sweep: u64 = 5;
+        let time: u64 = 123_456_789;
+        let channel: u64 = 3;
+        let word = (channel << 60) | (time << 21) | (sweep << 1);
+        let data = word.to_le_bytes();
+
+        let decoded = decode_binary(&data, &time_patch);
+        let LSTData::Binary {
+            sweep: sweeps,
+            time: times,
+            channel: channels,
+        } = decoded
+        else {
+            panic!("expected LSTData::Binary");
+        };
+        assert_eq!(sweeps, vec![sweep as u32]);
+        assert_eq!(times, vec![time as u32]);
+        assert_eq!(channels, vec![channel as u32]);
+    }
+
+    #[test]
+    fn test_decode_binary_time_patch_1a() {
+        let header = vec!["time_patch=1a".to_owned()];
+        let time_patch = TimePatch::parse_header(&header).unwrap();
+
+        let sweep: u64 = 42;
+        let time: u64 = 1234;
+        let channel: u64 = 2;
+        let word = (channel << 45) | (time << 16) | sweep;
+        let data = &word.to_le_bytes()[..6]; // binary_width = 48 bits = 6 bytes
+
+        let decoded = decode_binary(data, &time_patch);
+        let LSTData::Binary {
+            sweep: sweeps,
+            time: times,
+            channel: channels,
+        } = decoded
+        else {
+            panic!("expected LSTData::Binary");
+        };
+        assert_eq!(sweeps, vec![sweep as u32]);
+        assert_eq!(times, vec![time as u32]);
+        assert_eq!(channels, vec![channel as u32]);
+    }
+
+    #[test]
+    fn test_decode_binary_skips_zero_channel_and_trailing_bytes() {
+        let header = vec!["time_patch=9".to_owned()];
+        let time_patch = TimePatch::parse_header(&header).unwrap();
+
+        let padding_word: u64 = 0; // channel field is zero -> should be skipped
+        let real_word: u64 = (1u64 << 60) | (7u64 << 21) | (2u64 << 1);
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&padding_word.to_le_bytes());
+        data.extend_from_slice(&real_word.to_le_bytes());
+        data.extend_from_slice(&[0xAB, 0xCD, 0xEF]); // trailing partial word
+
+        let decoded = decode_binary(&data, &time_patch);
+        let LSTData::Binary {
+            sweep: sweeps,
+            time: times,
+            channel: channels,
+        } = decoded
+        else {
+            panic!("expected LSTData::Binary");
+        };
+        assert_eq!(sweeps, vec![2]);
+        assert_eq!(times, vec![7]);
+        assert_eq!(channels, vec![1]);
+    }
+
+    #[test]
+    fn test_partition_tagged_events() {
+        // Sweep 1: signal + tag -> tagged. Sweep 2: signal only -> untagged.
+        let data = LSTData::Binary {
+            sweep: vec![1, 1, 2],
+            time: vec![100, 200, 300],
+            channel: vec![1, 2, 1],
+        };
+
+        let events = partition_tagged_events(&data, 1, 2).unwrap();
+        assert_eq!(events.tagged, HashMap::from([(1, vec![100])]));
+        assert_eq!(events.untagged, HashMap::from([(2, vec![300])]));
+    }
+
+    #[test]
+    fn test_partition_tagged_events_none_for_ascii() {
+        let data = LSTData::ASCII(vec!["irrelevant".to_owned()]);
+        assert!(partition_tagged_events(&data, 1, 2).is_none());
+    }
+
+    fn test_file(data: LSTData, channel: u8, calibration_factor: f64) -> LSTFile {
+        LSTFile {
+            bin_width: BinWidth { value_ps: 100 },
+            calibration_factor: CalFactor {
+                value: calibration_factor,
+            },
+            data_type: DataType {
+                format: DataFormat::BINARY,
+                time_patch: TimePatch::parse_header(&vec!["time_patch=9".to_owned()]).unwrap(),
+            },
+            shot_range: ShotRange { value: 1 },
+            time_stamp: PrimitiveDateTime::new(time::Date::MIN, time::Time::MIDNIGHT),
+            data,
+            tagged_events: None,
+            channel,
+        }
+    }
+
+    #[test]
+    fn test_time_histogram() {
+        let data = LSTData::Binary {
+            sweep: vec![1, 1, 2],
+            time: vec![5, 5, 10],
+            channel: vec![1, 1, 1],
+        };
+        let file = test_file(data, 1, 2.0);
+
+        let (tof_ns, counts) = file.time_histogram();
+        assert_eq!(counts[5], 2);
+        assert_eq!(counts[10], 1);
+        assert_eq!(tof_ns[10], 1.0); // 10 channels * 100 ps / 1000 = 1.0 ns
+    }
+
+    #[test]
+    fn test_to_mass() {
+        let data = LSTData::Binary {
+            sweep: vec![],
+            time: vec![],
+            channel: vec![],
+        };
+        let file = test_file(data, 1, 2.0);
+
+        assert_eq!(file.to_mass(&[1.0, 2.0]), vec![2.0, 8.0]);
+    }
+
+    #[test]
+    fn test_decode_binary_parallel_matches_sequential() {
+        let header = vec!["time_patch=9".to_owned()];
+        let time_patch = TimePatch::parse_header(&header).unwrap();
+
+        // Enough words to span several chunks, plus a trailing partial word.
+        let mut data = Vec::new();
+        for i in 0..(3 * PARALLEL_DECODE_CHUNK_WORDS as u64 + 7) {
+            let word = (1u64 << 60) | ((i % 1000) << 21) | (i << 1);
+            data.extend_from_slice(&word.to_le_bytes());
+        }
+        data.extend_from_slice(&[0xAB, 0xCD, 0xEF]);
+
+        let sequential = decode_binary_sequential(&data, &time_patch);
+        let parallel = decode_binary_parallel(&data, &time_patch);
+
+        let LSTData::Binary {
+            sweep: seq_sweep,
+            time: seq_time,
+            channel: seq_channel,
+        } = sequential
+        else {
+            panic!("expected LSTData::Binary");
+        };
+        let LSTData::Binary {
+            sweep: par_sweep,
+            time: par_time,
+            channel: par_channel,
+        } = parallel
+        else {
+            panic!("expected LSTData::Binary");
+        };
+
+        assert_eq!(seq_sweep, par_sweep);
+        assert_eq!(seq_time, par_time);
+        assert_eq!(seq_channel, par_channel);
+    }
 }